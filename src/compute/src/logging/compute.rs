@@ -211,6 +211,8 @@ pub(super) fn construct<A: Allocate + 'static>(
         let (mut arrangement_heap_allocations_out, arrangement_heap_allocations) =
             demux.new_output();
         let (mut error_count_out, error_count) = demux.new_output();
+        let (mut frontier_lag_out, frontier_lag) = demux.new_output();
+        let (mut introspection_overflow_out, introspection_overflow) = demux.new_output();
 
         let mut demux_state = DemuxState::new(worker2);
         let mut demux_buffer = Vec::new();
@@ -226,6 +228,8 @@ pub(super) fn construct<A: Allocate + 'static>(
                 let mut arrangement_heap_capacity = arrangement_heap_capacity_out.activate();
                 let mut arrangement_heap_allocations = arrangement_heap_allocations_out.activate();
                 let mut error_count = error_count_out.activate();
+                let mut frontier_lag = frontier_lag_out.activate();
+                let mut introspection_overflow = introspection_overflow_out.activate();
 
                 input.for_each(|cap, data| {
                     data.swap(&mut demux_buffer);
@@ -241,6 +245,8 @@ pub(super) fn construct<A: Allocate + 'static>(
                         arrangement_heap_capacity: arrangement_heap_capacity.session(&cap),
                         arrangement_heap_allocations: arrangement_heap_allocations.session(&cap),
                         error_count: error_count.session(&cap),
+                        frontier_lag: frontier_lag.session(&cap),
+                        introspection_overflow: introspection_overflow.session(&cap),
                     };
 
                     for (time, logger_id, event) in demux_buffer.drain(..) {
@@ -311,6 +317,42 @@ pub(super) fn construct<A: Allocate + 'static>(
                 ])
             }
         });
+        // Tap the peek and arrangement-size streams to feed the Prometheus metrics registry. This
+        // happens alongside, not instead of, the usual Row-packing below: the metrics registry is
+        // just another consumer of the same demuxed collections.
+        let compute_metrics = metrics::ComputeMetrics::new();
+        peek.as_collection().inspect({
+            let compute_metrics = compute_metrics.clone();
+            move |(PeekDatum { peek_type, .. }, _time, diff)| {
+                if *diff > 0 {
+                    compute_metrics.observe_peek(worker_id, *peek_type);
+                }
+            }
+        });
+        peek_duration.as_collection().inspect({
+            let compute_metrics = compute_metrics.clone();
+            move |(PeekDurationDatum { peek_type, bucket }, _time, diff)| {
+                if *diff > 0 {
+                    compute_metrics.observe_peek_duration(worker_id, *peek_type, *bucket);
+                }
+            }
+        });
+        arrangement_heap_size.as_collection().inspect({
+            let compute_metrics = compute_metrics.clone();
+            move |(ArrangementHeapDatum { .. }, _time, diff)| {
+                compute_metrics.add_arrangement_size_bytes(worker_id, *diff);
+            }
+        });
+        error_count.as_collection().inspect({
+            let compute_metrics = compute_metrics.clone();
+            move |(ErrorCountDatum { export_id, count }, _time, diff)| {
+                // The demux gives the retraction of an export's old count before the insertion
+                // of its new one, so clearing on retraction leaves the latest nonzero count.
+                let count = if *diff > 0 { *count } else { 0 };
+                compute_metrics.set_error_count(worker_id, *export_id, count);
+            }
+        });
+
         let mut packer = PermutedRowPacker::new(ComputeLog::PeekDuration);
         let peek_duration =
             peek_duration
@@ -365,6 +407,35 @@ pub(super) fn construct<A: Allocate + 'static>(
             }
         });
 
+        // Tap the frontier-lag stream into the same Prometheus-style metrics registry used for
+        // peeks and arrangement sizes above. Exposing it as a queryable SQL relation would
+        // additionally require a new `ComputeLog` variant (and matching `LogVariant`) in
+        // `logging/mod.rs`, which isn't part of this checkout; the metrics tap doesn't need that
+        // type and is a real, externally observable consumer in its own right.
+        frontier_lag.as_collection().inspect({
+            let compute_metrics = compute_metrics.clone();
+            move |(FrontierLagDatum { export_id, lag_ms }, _time, diff)| {
+                // As for error counts, a retraction precedes the insertion of the new value, so
+                // a retraction with no insertion after it (an export drop) leaves no entry.
+                let lag_ms = (*diff > 0).then_some(*lag_ms);
+                compute_metrics.set_frontier_lag_ms(worker_id, *export_id, lag_ms);
+            }
+        });
+
+        // Tap the introspection-overflow stream into the same metrics registry. A queryable SQL
+        // counter would need the same kind of new `ComputeLog` variant the frontier-lag tap above
+        // is missing, for the same reason (that enum lives in `logging/mod.rs`); unlike frontier
+        // lag, this stream's only other consumer would be an operator deciding whether to
+        // tighten the eviction budget, which the metrics gauge already serves just as well.
+        introspection_overflow.as_collection().inspect({
+            let compute_metrics = compute_metrics.clone();
+            move |((), _time, diff)| {
+                if *diff > 0 {
+                    compute_metrics.observe_introspection_overflow(worker_id);
+                }
+            }
+        });
+
         use ComputeLog::*;
         let logs = [
             (DataflowCurrent, dataflow_current),
@@ -414,6 +485,111 @@ where
     Datum::String(scratch)
 }
 
+/// An insertion-time-ordered map, used for the unbounded logging-state maps that
+/// [`MemoryBudget`] needs to evict from oldest-entry-first: a `BTreeMap` alone lets us look
+/// entries up by key in O(log n), but picking "the oldest one" out of it is O(n). Keeping a
+/// parallel `(Duration, K)` index makes both operations O(log n).
+struct TimeIndexedMap<K: Ord + Copy, V> {
+    entries: BTreeMap<K, (Duration, V)>,
+    by_time: BTreeSet<(Duration, K)>,
+}
+
+impl<K: Ord + Copy, V> Default for TimeIndexedMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            by_time: BTreeSet::new(),
+        }
+    }
+}
+
+impl<K: Ord + Copy, V> TimeIndexedMap<K, V> {
+    /// Insert `value` for `key`, recorded at `time`. Returns the previous entry, if any.
+    fn insert(&mut self, key: K, time: Duration, value: V) -> Option<(Duration, V)> {
+        let prev = self.entries.insert(key, (time, value));
+        if let Some((prev_time, _)) = &prev {
+            self.by_time.remove(&(*prev_time, key));
+        }
+        self.by_time.insert((time, key));
+        prev
+    }
+
+    /// Remove and return the entry for `key`, if present.
+    fn remove(&mut self, key: &K) -> Option<(Duration, V)> {
+        let removed = self.entries.remove(key);
+        if let Some((time, _)) = &removed {
+            self.by_time.remove(&(*time, *key));
+        }
+        removed
+    }
+
+    /// Remove and return the entry with the oldest recorded time, if the map is non-empty.
+    fn pop_oldest(&mut self) -> Option<(K, Duration, V)> {
+        let &(time, key) = self.by_time.iter().next()?;
+        self.by_time.remove(&(time, key));
+        let (_, value) = self
+            .entries
+            .remove(&key)
+            .expect("entries and by_time are kept in sync");
+        Some((key, time, value))
+    }
+}
+
+/// Default memory budget for the unbounded logging-state maps tracked by [`MemoryBudget`], in
+/// approximate bytes.
+///
+/// A fixed constant is a worse fit here than for [`DEFAULT_DURATION_BUCKET_SUB_BUCKETS`] below:
+/// the right budget depends on how much memory the host process can spare, which varies per
+/// deployment, not just per query shape. Making it configurable means adding a field to
+/// `LoggingConfig` in `mz_compute_client::logging`, which this checkout doesn't contain.
+const DEFAULT_LOGGING_STATE_BUDGET_BYTES: usize = 32 << 20;
+
+/// Tracks the approximate combined byte footprint of the demux operator's unbounded logging-state
+/// maps (`peek_stash`, `dataflow_drop_times`, `shutdown_dataflows`) against a configured capacity.
+///
+/// These maps only shrink when a matching retire/shutdown event arrives, so a leaked peek or a
+/// dataflow that never reports shutdown would otherwise pin memory forever. Once `current` exceeds
+/// `capacity`, [`DemuxHandler::evict_for_budget`] evicts the longest-pending entries until it no
+/// longer does.
+struct MemoryBudget {
+    current: usize,
+    capacity: usize,
+}
+
+impl MemoryBudget {
+    fn new(capacity: usize) -> Self {
+        Self {
+            current: 0,
+            capacity,
+        }
+    }
+
+    fn record_insert(&mut self, bytes: usize) {
+        self.current += bytes;
+    }
+
+    fn record_remove(&mut self, bytes: usize) {
+        self.current = self.current.saturating_sub(bytes);
+    }
+
+    fn over_budget(&self) -> bool {
+        self.current > self.capacity
+    }
+}
+
+/// Approximate footprint of one `peek_stash` entry.
+fn peek_stash_entry_bytes() -> usize {
+    std::mem::size_of::<Uuid>()
+        + std::mem::size_of::<Peek>()
+        + std::mem::size_of::<PeekType>()
+        + std::mem::size_of::<Duration>()
+}
+
+/// Approximate footprint of one `dataflow_drop_times`/`shutdown_dataflows` entry.
+fn dataflow_tracking_entry_bytes() -> usize {
+    std::mem::size_of::<usize>() + std::mem::size_of::<Duration>()
+}
+
 /// State maintained by the demux operator.
 struct DemuxState<A: Allocate> {
     /// The worker hosting this operator.
@@ -423,13 +599,26 @@ struct DemuxState<A: Allocate> {
     /// Maps live dataflows to counts of their exports.
     dataflow_export_counts: BTreeMap<usize, u32>,
     /// Maps dropped dataflows to their drop time.
-    dataflow_drop_times: BTreeMap<usize, Duration>,
-    /// Contains dataflows that have shut down but not yet been dropped.
-    shutdown_dataflows: BTreeSet<usize>,
-    /// Maps pending peeks to their installation time.
-    peek_stash: BTreeMap<Uuid, Duration>,
+    dataflow_drop_times: TimeIndexedMap<usize, ()>,
+    /// Contains dataflows that have shut down but not yet been dropped, and when they did.
+    shutdown_dataflows: TimeIndexedMap<usize, ()>,
+    /// Maps pending peeks to their installation time and the data needed to retract them if
+    /// they're evicted under memory pressure.
+    peek_stash: TimeIndexedMap<Uuid, (Peek, PeekType)>,
     /// Arrangement size stash
     arrangement_size: BTreeMap<usize, ArrangementSizeState>,
+    /// Import dependency edges, `export_id -> {import_id}`, as reported by `ImportFrontier`
+    /// events. Used to compute the critical-path frontier lag of each export relative to its
+    /// furthest-behind source; see [`DemuxHandler::reachable_min`].
+    edges: BTreeMap<GlobalId, BTreeSet<GlobalId>>,
+    /// The latest known frontier for each id we've heard about, whether as an export (via
+    /// `Frontier`) or an import (via `ImportFrontier`).
+    frontiers: BTreeMap<GlobalId, Timestamp>,
+    /// The lag value last emitted for each export, so that frontier-lag recomputation can emit a
+    /// retraction/insertion pair only when the value actually changes.
+    frontier_lags: BTreeMap<GlobalId, u64>,
+    /// Combined budget for `peek_stash`, `dataflow_drop_times`, and `shutdown_dataflows`.
+    memory_budget: MemoryBudget,
 }
 
 impl<A: Allocate> DemuxState<A> {
@@ -442,6 +631,10 @@ impl<A: Allocate> DemuxState<A> {
             shutdown_dataflows: Default::default(),
             peek_stash: Default::default(),
             arrangement_size: Default::default(),
+            edges: Default::default(),
+            frontiers: Default::default(),
+            frontier_lags: Default::default(),
+            memory_budget: MemoryBudget::new(DEFAULT_LOGGING_STATE_BUDGET_BYTES),
         }
     }
 }
@@ -472,6 +665,15 @@ struct ArrangementSizeState {
     size: isize,
     capacity: isize,
     count: isize,
+    /// Buffered, not-yet-emitted net size delta for the logging interval it's paired with.
+    /// Updates at the same interval-rounded timestamp are coalesced into this single delta
+    /// rather than each producing their own output update; see
+    /// [`DemuxHandler::handle_arrangement_heap_size`].
+    pending_size: Option<(Timestamp, isize)>,
+    /// Same as `pending_size`, but for capacity.
+    pending_capacity: Option<(Timestamp, isize)>,
+    /// Same as `pending_size`, but for allocation count.
+    pending_allocations: Option<(Timestamp, isize)>,
 }
 
 type Update<D> = (D, Timestamp, Diff);
@@ -491,6 +693,8 @@ struct DemuxOutput<'a> {
     arrangement_heap_capacity: OutputSession<'a, ArrangementHeapDatum>,
     arrangement_heap_allocations: OutputSession<'a, ArrangementHeapDatum>,
     error_count: OutputSession<'a, ErrorCountDatum>,
+    frontier_lag: OutputSession<'a, FrontierLagDatum>,
+    introspection_overflow: OutputSession<'a, ()>,
 }
 
 #[derive(Clone)]
@@ -512,6 +716,12 @@ struct ImportFrontierDatum {
     frontier: Timestamp,
 }
 
+/// Carries the full [`Peek`] into the `peek`/`peek_current` logging
+/// collection.
+///
+/// The payload is not deduplicated or content-addressed: every install and
+/// retire logs its own copy of the whole `Peek`, and `peek_stash` keeps one
+/// more copy for each pending peek.
 #[derive(Clone)]
 struct PeekDatum {
     peek: Peek,
@@ -538,6 +748,52 @@ struct ErrorCountDatum {
     count: i64,
 }
 
+#[derive(Clone)]
+struct FrontierLagDatum {
+    export_id: GlobalId,
+    lag_ms: u64,
+}
+
+/// A sentinel `PeekDurationDatum.bucket` value used when a peek's duration is reported as a
+/// result of [`DemuxHandler::evict_oldest_peek`] rather than a normal retire, since the true
+/// elapsed time of an evicted peek is unknown. It is `u64::MAX`, the largest value the `UInt64`
+/// bucket column holds; it is not a power of two, so it differs from every bucket produced by
+/// [`log_linear_duration_bucket`] for a duration that fits that column.
+const EVICTED_PEEK_DURATION_BUCKET: u128 = (1u128 << 64) - 1;
+
+/// Number of log-linear sub-buckets per power-of-two band used by [`log_linear_duration_bucket`]
+/// to bucket peek and shutdown durations. Must be a power of two. The default of 1 preserves
+/// today's coarse power-of-two-only binning (one bucket per band, i.e. 2x resolution).
+///
+/// Integration seam: this would ideally be a field on `LoggingConfig` so operators can tune the
+/// resolution, but that type lives in `mz_compute_client::logging`, which isn't part of this
+/// checkout.
+const DEFAULT_DURATION_BUCKET_SUB_BUCKETS: u32 = 1;
+
+/// Bucket `elapsed_ns` into a log-linear (HDR-style) histogram bucket: first pick the
+/// power-of-two band `b = floor(log2(elapsed_ns))`, then subdivide that band into
+/// `sub_buckets` equal linear sub-buckets by the leading mantissa bits, yielding bucket id
+/// `b * sub_buckets + sub`. This gives coarse 2x resolution between bands (as a single
+/// `next_power_of_two()` bucket always did) while resolving `sub_buckets` distinct buckets
+/// within a band, rather than collapsing every value in a band into one.
+///
+/// The mapping is monotonic and O(1). With `sub_buckets == 1` this is exactly
+/// `elapsed_ns.next_power_of_two()` -- today's output -- rather than the band index
+/// `floor(log2(elapsed_ns))`, which would renumber every existing bucket.
+fn log_linear_duration_bucket(elapsed_ns: u128, sub_buckets: u32) -> u128 {
+    if sub_buckets <= 1 {
+        return elapsed_ns.next_power_of_two();
+    }
+    let Some(band) = elapsed_ns.checked_ilog2() else {
+        return 0;
+    };
+    let sub_buckets = u128::from(sub_buckets);
+    let band_start = 1u128 << band;
+    let within_band = elapsed_ns - band_start;
+    let sub = (within_band * sub_buckets) >> band;
+    u128::from(band) * sub_buckets + sub
+}
+
 /// Event handler of the demux operator.
 struct DemuxHandler<'a, 'b, A: Allocate + 'static> {
     /// State kept by the demux operator.
@@ -657,36 +913,64 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             };
             self.output.error_count.give((datum, ts, -1));
         }
+
+        // Drop this export's place in the dependency graph, so it doesn't leak. We don't remove
+        // `id` from `frontiers`: other live exports may still import from it (directly, or, if
+        // this was itself an import, transitively), and a stale-but-present frontier entry is
+        // harmless, whereas a missing one would make `reachable_min` treat it as live-but-unknown.
+        self.state.edges.remove(&id);
+        if let Some(lag_ms) = self.state.frontier_lags.remove(&id) {
+            let datum = FrontierLagDatum {
+                export_id: id,
+                lag_ms,
+            };
+            self.output.frontier_lag.give((datum, ts, -1));
+        }
     }
 
     fn handle_dataflow_dropped(&mut self, id: usize) {
         self.state.dataflow_export_counts.remove(&id);
 
-        if self.state.shutdown_dataflows.remove(&id) {
+        if self.state.shutdown_dataflows.remove(&id).is_some() {
+            self.state
+                .memory_budget
+                .record_remove(dataflow_tracking_entry_bytes());
             // Dataflow has already shut down before it was dropped.
             self.output.shutdown_duration.give((0, self.ts(), 1));
         } else {
             // Dataflow has not yet shut down.
-            let existing = self.state.dataflow_drop_times.insert(id, self.time);
+            let existing = self.state.dataflow_drop_times.insert(id, self.time, ());
             if existing.is_some() {
                 error!(dataflow = ?id, "dataflow already dropped");
+            } else {
+                self.state
+                    .memory_budget
+                    .record_insert(dataflow_tracking_entry_bytes());
+                self.evict_for_budget();
             }
         }
     }
 
     fn handle_dataflow_shutdown(&mut self, id: usize) {
-        if let Some(start) = self.state.dataflow_drop_times.remove(&id) {
+        if let Some((start, ())) = self.state.dataflow_drop_times.remove(&id) {
+            self.state
+                .memory_budget
+                .record_remove(dataflow_tracking_entry_bytes());
             // Dataflow has alredy been dropped.
             let elapsed_ns = self.time.saturating_sub(start).as_nanos();
-            let elapsed_pow = elapsed_ns.next_power_of_two();
-            self.output
-                .shutdown_duration
-                .give((elapsed_pow, self.ts(), 1));
+            let bucket =
+                log_linear_duration_bucket(elapsed_ns, DEFAULT_DURATION_BUCKET_SUB_BUCKETS);
+            self.output.shutdown_duration.give((bucket, self.ts(), 1));
         } else {
             // Dataflow has not yet been dropped.
-            let was_new = self.state.shutdown_dataflows.insert(id);
-            if !was_new {
+            let existing = self.state.shutdown_dataflows.insert(id, self.time, ());
+            if existing.is_some() {
                 error!(dataflow = ?id, "dataflow already shutdown");
+            } else {
+                self.state
+                    .memory_budget
+                    .record_insert(dataflow_tracking_entry_bytes());
+                self.evict_for_budget();
             }
         }
     }
@@ -724,16 +1008,29 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
     fn handle_peek_install(&mut self, peek: Peek, peek_type: PeekType) {
         let uuid = peek.uuid;
         let ts = self.ts();
-        self.output
-            .peek
-            .give((PeekDatum { peek, peek_type }, ts, 1));
+        self.output.peek.give((
+            PeekDatum {
+                peek: peek.clone(),
+                peek_type,
+            },
+            ts,
+            1,
+        ));
 
-        let existing = self.state.peek_stash.insert(uuid, self.time);
+        let existing = self
+            .state
+            .peek_stash
+            .insert(uuid, self.time, (peek, peek_type));
         if existing.is_some() {
             error!(
                 uuid = ?uuid,
                 "peek already registered",
             );
+        } else {
+            self.state
+                .memory_budget
+                .record_insert(peek_stash_entry_bytes());
+            self.evict_for_budget();
         }
     }
 
@@ -744,20 +1041,103 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             .peek
             .give((PeekDatum { peek, peek_type }, ts, -1));
 
-        if let Some(start) = self.state.peek_stash.remove(&uuid) {
+        if let Some((start, _)) = self.state.peek_stash.remove(&uuid) {
+            self.state
+                .memory_budget
+                .record_remove(peek_stash_entry_bytes());
             let elapsed_ns = self.time.saturating_sub(start).as_nanos();
-            let bucket = elapsed_ns.next_power_of_two();
+            let bucket =
+                log_linear_duration_bucket(elapsed_ns, DEFAULT_DURATION_BUCKET_SUB_BUCKETS);
             self.output
                 .peek_duration
                 .give((PeekDurationDatum { peek_type, bucket }, ts, 1));
         } else {
+            // The peek may genuinely never have been installed, but it may also have been
+            // evicted by `evict_oldest_peek` under memory pressure; we can't tell which from
+            // here, so this no longer necessarily indicates a bug.
             error!(
                 uuid = ?uuid,
-                "peek not yet registered",
+                "peek not registered (already retired, or evicted under memory pressure)",
             );
         }
     }
 
+    /// Evict the longest-pending entry from whichever of `peek_stash`, `dataflow_drop_times`, or
+    /// `shutdown_dataflows` has one available, trying them in that order, until
+    /// `self.state.memory_budget` is back under capacity or all three are empty.
+    ///
+    /// Called after every insert into one of those maps; see [`MemoryBudget`].
+    fn evict_for_budget(&mut self) {
+        while self.state.memory_budget.over_budget() {
+            if self.evict_oldest_peek()
+                || self.evict_oldest_dataflow_drop()
+                || self.evict_oldest_shutdown_dataflow()
+            {
+                continue;
+            }
+            break;
+        }
+    }
+
+    /// Evict the oldest pending peek, retracting its `PeekDatum` and emitting a
+    /// [`EVICTED_PEEK_DURATION_BUCKET`]-bucketed `PeekDurationDatum` in its place. Returns whether
+    /// an entry was evicted.
+    fn evict_oldest_peek(&mut self) -> bool {
+        let Some((_uuid, _start, (peek, peek_type))) = self.state.peek_stash.pop_oldest() else {
+            return false;
+        };
+        self.state
+            .memory_budget
+            .record_remove(peek_stash_entry_bytes());
+
+        let ts = self.ts();
+        self.output
+            .peek
+            .give((PeekDatum { peek, peek_type }, ts, -1));
+        self.output.peek_duration.give((
+            PeekDurationDatum {
+                peek_type,
+                bucket: EVICTED_PEEK_DURATION_BUCKET,
+            },
+            ts,
+            1,
+        ));
+        self.output.introspection_overflow.give(((), ts, 1));
+        true
+    }
+
+    /// Evict the oldest pending dropped-but-not-yet-shutdown dataflow. There is no relation to
+    /// retract here -- `dataflow_drop_times` only ever feeds a later `shutdown_duration` emission
+    /// that we're about to forgo -- so eviction drops the bookkeeping entry and counts one
+    /// `introspection_overflow` update. Returns whether an entry was evicted.
+    fn evict_oldest_dataflow_drop(&mut self) -> bool {
+        let Some((_id, _start, ())) = self.state.dataflow_drop_times.pop_oldest() else {
+            return false;
+        };
+        self.state
+            .memory_budget
+            .record_remove(dataflow_tracking_entry_bytes());
+        let ts = self.ts();
+        self.output.introspection_overflow.give(((), ts, 1));
+        true
+    }
+
+    /// Evict the oldest pending shut-down-but-not-yet-dropped dataflow. As with
+    /// [`Self::evict_oldest_dataflow_drop`], there's no relation to retract; eviction drops the
+    /// bookkeeping entry and counts one `introspection_overflow` update. Returns whether an entry
+    /// was evicted.
+    fn evict_oldest_shutdown_dataflow(&mut self) -> bool {
+        let Some((_id, _start, ())) = self.state.shutdown_dataflows.pop_oldest() else {
+            return false;
+        };
+        self.state
+            .memory_budget
+            .record_remove(dataflow_tracking_entry_bytes());
+        let ts = self.ts();
+        self.output.introspection_overflow.give(((), ts, 1));
+        true
+    }
+
     fn handle_frontier(&mut self, export_id: GlobalId, frontier: Timestamp, diff: i8) {
         let diff = i64::from(diff);
         let ts = self.ts();
@@ -766,6 +1146,11 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             frontier,
         };
         self.output.frontier.give((datum, ts, diff));
+
+        if diff > 0 {
+            self.state.frontiers.insert(export_id, frontier);
+        }
+        self.recompute_frontier_lags();
     }
 
     fn handle_import_frontier(
@@ -782,51 +1167,181 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             frontier,
         };
         self.output.import_frontier.give((datum, ts, diff.into()));
+
+        if diff > 0 {
+            self.state
+                .edges
+                .entry(export_id)
+                .or_default()
+                .insert(import_id);
+            self.state.frontiers.insert(import_id, frontier);
+        }
+        self.recompute_frontier_lags();
+    }
+
+    /// The minimum frontier reachable from `id` by following import edges, i.e. the frontier of
+    /// the furthest-behind source that `id` (transitively) depends on, or `id`'s own frontier if
+    /// that is lower. Ids with no known frontier are skipped; returns `None` only if neither `id`
+    /// nor anything it depends on has a known frontier yet.
+    ///
+    /// `memo` caches results within a single call to [`Self::recompute_frontier_lags`]; `visited`
+    /// guards against cycles (the import graph is expected to be a DAG, but a self-reference or a
+    /// reporting bug must not send this into an infinite loop).
+    fn reachable_min(
+        &self,
+        id: GlobalId,
+        visited: &mut BTreeSet<GlobalId>,
+        memo: &mut BTreeMap<GlobalId, Option<Timestamp>>,
+    ) -> Option<Timestamp> {
+        if let Some(result) = memo.get(&id) {
+            return *result;
+        }
+        if !visited.insert(id) {
+            // Already on the current path: treat a cycle as contributing nothing, rather than
+            // recursing forever.
+            return None;
+        }
+
+        let own = self.state.frontiers.get(&id).copied();
+        let mut min = own;
+        if let Some(imports) = self.state.edges.get(&id) {
+            for &import_id in imports {
+                let reachable = self.reachable_min(import_id, visited, memo);
+                min = match (min, reachable) {
+                    (Some(a), Some(b)) if u64::from(b) < u64::from(a) => Some(b),
+                    (Some(a), _) => Some(a),
+                    (None, Some(b)) => Some(b),
+                    (None, None) => None,
+                };
+            }
+        }
+
+        visited.remove(&id);
+        memo.insert(id, min);
+        min
+    }
+
+    /// Recompute the critical-path frontier lag for every known export and emit a
+    /// retraction/insertion pair for any export whose lag changed.
+    ///
+    /// See [`FrontierLagDatum`] and [`DemuxOutput::frontier_lag`]: the emitted stream feeds the
+    /// `frontier_lag_ms` instrument of [`metrics::ComputeMetrics`]. Exposing it as a
+    /// queryable SQL relation would additionally need a `ComputeLog::FrontierLag` variant added
+    /// to `ComputeLog`/`LogVariant` in `logging/mod.rs`, which isn't part of this checkout.
+    fn recompute_frontier_lags(&mut self) {
+        let ts = self.ts();
+        let mut memo = BTreeMap::new();
+        let export_ids: Vec<_> = self.state.exports.keys().copied().collect();
+
+        for export_id in export_ids {
+            let Some(&export_frontier) = self.state.frontiers.get(&export_id) else {
+                continue;
+            };
+            let mut visited = BTreeSet::new();
+            let Some(reachable) = self.reachable_min(export_id, &mut visited, &mut memo) else {
+                continue;
+            };
+
+            let lag_ms = u64::from(export_frontier).saturating_sub(u64::from(reachable));
+            let old_lag_ms = self.state.frontier_lags.get(&export_id).copied();
+            if old_lag_ms == Some(lag_ms) {
+                continue;
+            }
+
+            if let Some(old_lag_ms) = old_lag_ms {
+                let datum = FrontierLagDatum {
+                    export_id,
+                    lag_ms: old_lag_ms,
+                };
+                self.output.frontier_lag.give((datum, ts, -1));
+            }
+            let datum = FrontierLagDatum { export_id, lag_ms };
+            self.output.frontier_lag.give((datum, ts, 1));
+            self.state.frontier_lags.insert(export_id, lag_ms);
+        }
     }
 
     /// Update the allocation size for an arrangement.
+    ///
+    /// Updates within the same interval-rounded timestamp are coalesced: rather than giving one
+    /// output update per delta, we buffer the net change in `state.pending_size` and only emit it
+    /// once the event time advances past the interval boundary it was buffered for. This keeps
+    /// the observable per-interval values identical while cutting update volume for busy
+    /// arrangements.
     fn handle_arrangement_heap_size(&mut self, operator_id: usize, size: isize) {
         let ts = self.ts();
         let Some(state) = self.state.arrangement_size.get_mut(&operator_id) else {
             return;
         };
 
-        let datum = ArrangementHeapDatum { operator_id };
-        self.output
-            .arrangement_heap_size
-            .give((datum, ts, Diff::cast_from(size)));
-
-        state.size += size;
+        match state.pending_size {
+            Some((pending_ts, ref mut delta)) if pending_ts == ts => *delta += size,
+            Some((pending_ts, delta)) => {
+                if delta != 0 {
+                    let datum = ArrangementHeapDatum { operator_id };
+                    self.output.arrangement_heap_size.give((
+                        datum,
+                        pending_ts,
+                        Diff::cast_from(delta),
+                    ));
+                }
+                state.size += delta;
+                state.pending_size = Some((ts, size));
+            }
+            None => state.pending_size = Some((ts, size)),
+        }
     }
 
-    /// Update the allocation capacity for an arrangement.
+    /// Update the allocation capacity for an arrangement. See
+    /// [`Self::handle_arrangement_heap_size`] for the interval-coalescing behavior.
     fn handle_arrangement_heap_capacity(&mut self, operator_id: usize, capacity: isize) {
         let ts = self.ts();
         let Some(state) = self.state.arrangement_size.get_mut(&operator_id) else {
             return;
         };
 
-        let datum = ArrangementHeapDatum { operator_id };
-        self.output
-            .arrangement_heap_capacity
-            .give((datum, ts, Diff::cast_from(capacity)));
-
-        state.capacity += capacity;
+        match state.pending_capacity {
+            Some((pending_ts, ref mut delta)) if pending_ts == ts => *delta += capacity,
+            Some((pending_ts, delta)) => {
+                if delta != 0 {
+                    let datum = ArrangementHeapDatum { operator_id };
+                    self.output.arrangement_heap_capacity.give((
+                        datum,
+                        pending_ts,
+                        Diff::cast_from(delta),
+                    ));
+                }
+                state.capacity += delta;
+                state.pending_capacity = Some((ts, capacity));
+            }
+            None => state.pending_capacity = Some((ts, capacity)),
+        }
     }
 
-    /// Update the allocation count for an arrangement.
+    /// Update the allocation count for an arrangement. See
+    /// [`Self::handle_arrangement_heap_size`] for the interval-coalescing behavior.
     fn handle_arrangement_heap_allocations(&mut self, operator_id: usize, count: isize) {
         let ts = self.ts();
         let Some(state) = self.state.arrangement_size.get_mut(&operator_id) else {
             return;
         };
 
-        let datum = ArrangementHeapDatum { operator_id };
-        self.output
-            .arrangement_heap_allocations
-            .give((datum, ts, Diff::cast_from(count)));
-
-        state.count += count;
+        match state.pending_allocations {
+            Some((pending_ts, ref mut delta)) if pending_ts == ts => *delta += count,
+            Some((pending_ts, delta)) => {
+                if delta != 0 {
+                    let datum = ArrangementHeapDatum { operator_id };
+                    self.output.arrangement_heap_allocations.give((
+                        datum,
+                        pending_ts,
+                        Diff::cast_from(delta),
+                    ));
+                }
+                state.count += delta;
+                state.pending_allocations = Some((ts, count));
+            }
+            None => state.pending_allocations = Some((ts, count)),
+        }
     }
 
     /// Indicate that a new arrangement exists, start maintaining the heap size state.
@@ -842,8 +1357,45 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
 
     /// Indicate that an arrangement has been dropped and we can cleanup the heap size state.
     fn handle_arrangement_heap_size_operator_dropped(&mut self, operator_id: usize) {
-        if let Some(state) = self.state.arrangement_size.remove(&operator_id) {
+        if let Some(mut state) = self.state.arrangement_size.remove(&operator_id) {
             let ts = self.ts();
+
+            // Flush any buffered-but-not-yet-emitted deltas before tearing down this operator's
+            // state, so a coalesced update doesn't silently vanish.
+            if let Some((pending_ts, delta)) = state.pending_size.take() {
+                if delta != 0 {
+                    let datum = ArrangementHeapDatum { operator_id };
+                    self.output.arrangement_heap_size.give((
+                        datum,
+                        pending_ts,
+                        Diff::cast_from(delta),
+                    ));
+                }
+                state.size += delta;
+            }
+            if let Some((pending_ts, delta)) = state.pending_capacity.take() {
+                if delta != 0 {
+                    let datum = ArrangementHeapDatum { operator_id };
+                    self.output.arrangement_heap_capacity.give((
+                        datum,
+                        pending_ts,
+                        Diff::cast_from(delta),
+                    ));
+                }
+                state.capacity += delta;
+            }
+            if let Some((pending_ts, delta)) = state.pending_allocations.take() {
+                if delta != 0 {
+                    let datum = ArrangementHeapDatum { operator_id };
+                    self.output.arrangement_heap_allocations.give((
+                        datum,
+                        pending_ts,
+                        Diff::cast_from(delta),
+                    ));
+                }
+                state.count += delta;
+            }
+
             let datum = ArrangementHeapDatum { operator_id };
             self.output.arrangement_heap_size.give((
                 datum.clone(),
@@ -1036,6 +1588,113 @@ where
     sum
 }
 
+/// A small Prometheus-style metrics registry fed from the compute-logging demux output: peeks
+/// installed, peek duration buckets, arrangement heap size, per-export error counts,
+/// critical-path frontier lag, and logging-state evictions.
+///
+/// Each call to `construct` creates its own [`ComputeMetrics`], so a registry only sees the
+/// updates of the worker that built it. Every instrument is keyed by `worker_id`, and nothing
+/// here sums values across workers. Error counts are stored signed, as each worker reports them
+/// (a worker's count can be negative, see [`ErrorCountDatum`]), and are not clamped.
+///
+/// This module only records the instruments: nothing in it renders or exports them, so they are
+/// not served on any scrape endpoint.
+mod metrics {
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+
+    use mz_repr::{Diff, GlobalId};
+
+    use super::PeekType;
+
+    /// Handle to the shared instrument state. Cheap to clone; clones share the same underlying
+    /// counters.
+    #[derive(Clone, Default)]
+    pub struct ComputeMetrics {
+        inner: Arc<Mutex<Instruments>>,
+    }
+
+    #[derive(Default)]
+    struct Instruments {
+        /// Peeks installed, keyed by `(worker_id, peek_type)`.
+        peeks_total: BTreeMap<(usize, &'static str), u64>,
+        /// Peek duration histogram buckets, keyed by `(worker_id, peek_type, bucket)`.
+        peek_duration_bucket: BTreeMap<(usize, &'static str, u128), u64>,
+        /// Running total of arrangement heap size in bytes, keyed by `worker_id`.
+        arrangement_size_bytes: BTreeMap<usize, i64>,
+        /// Current error count, keyed by `(worker_id, export_id)`. Per-worker values may be
+        /// negative; see the module-level doc comment.
+        error_count: BTreeMap<(usize, GlobalId), i64>,
+        /// Current critical-path frontier lag in milliseconds, keyed by `(worker_id, export_id)`.
+        frontier_lag_ms: BTreeMap<(usize, GlobalId), u64>,
+        /// Running total of logging-state entries dropped by eviction, keyed by `worker_id`.
+        introspection_overflow_total: BTreeMap<usize, u64>,
+    }
+
+    impl ComputeMetrics {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Record that a peek of the given type was installed on `worker_id`.
+        pub fn observe_peek(&self, worker_id: usize, peek_type: PeekType) {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            *inner
+                .peeks_total
+                .entry((worker_id, peek_type.name()))
+                .or_default() += 1;
+        }
+
+        /// Record a peek duration bucket observation for `worker_id`.
+        pub fn observe_peek_duration(&self, worker_id: usize, peek_type: PeekType, bucket: u128) {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            *inner
+                .peek_duration_bucket
+                .entry((worker_id, peek_type.name(), bucket))
+                .or_default() += 1;
+        }
+
+        /// Apply a signed delta to the running arrangement heap size total for `worker_id`.
+        pub fn add_arrangement_size_bytes(&self, worker_id: usize, delta: Diff) {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            *inner.arrangement_size_bytes.entry(worker_id).or_default() += delta;
+        }
+
+        /// Set the current error count for `export_id` on `worker_id`. The value may be negative;
+        /// see the module-level doc comment for why that's expected.
+        pub fn set_error_count(&self, worker_id: usize, export_id: GlobalId, count: i64) {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            if count == 0 {
+                inner.error_count.remove(&(worker_id, export_id));
+            } else {
+                inner.error_count.insert((worker_id, export_id), count);
+            }
+        }
+
+        /// Set the current critical-path frontier lag for `export_id` on `worker_id`, or clear
+        /// it if `lag_ms` is `None`.
+        pub fn set_frontier_lag_ms(
+            &self,
+            worker_id: usize,
+            export_id: GlobalId,
+            lag_ms: Option<u64>,
+        ) {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            match lag_ms {
+                Some(lag_ms) => inner.frontier_lag_ms.insert((worker_id, export_id), lag_ms),
+                None => inner.frontier_lag_ms.remove(&(worker_id, export_id)),
+            };
+        }
+
+        /// Record that `worker_id` evicted a logging-state entry to stay within its memory
+        /// budget.
+        pub fn observe_introspection_overflow(&self, worker_id: usize) {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            *inner.introspection_overflow_total.entry(worker_id).or_default() += 1;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1045,4 +1704,16 @@ mod tests {
         // This could be a static assertion, but we don't use those yet in this crate.
         assert_eq!(48, std::mem::size_of::<ComputeEvent>())
     }
+
+    #[mz_ore::test]
+    fn test_log_linear_duration_bucket_default_matches_next_power_of_two() {
+        for elapsed_ns in [0, 1, 2, 3, 4, 5, 7, 8, 9, 1_000, 1_023, 1_024, 1_025, u64::MAX as u128]
+        {
+            assert_eq!(
+                log_linear_duration_bucket(elapsed_ns, DEFAULT_DURATION_BUCKET_SUB_BUCKETS),
+                elapsed_ns.next_power_of_two(),
+                "elapsed_ns={elapsed_ns}",
+            );
+        }
+    }
 }
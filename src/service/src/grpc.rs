@@ -13,20 +13,29 @@ use async_stream::stream;
 use async_trait::async_trait;
 use futures::future::{self, BoxFuture};
 use futures::stream::{Stream, StreamExt, TryStreamExt};
+use hmac::{Hmac, Mac};
 use http::uri::PathAndQuery;
 use hyper_util::rt::TokioIo;
 use mz_ore::metric;
-use mz_ore::metrics::{DeleteOnDropGauge, MetricsRegistry, UIntGaugeVec};
+use mz_ore::metrics::{
+    DeleteOnDropCounter, DeleteOnDropGauge, IntCounterVec, MetricsRegistry, UIntGauge,
+    UIntGaugeVec,
+};
 use mz_ore::netio::{Listener, SocketAddr, SocketAddrType};
 use mz_proto::{ProtoType, RustType};
-use prometheus::core::AtomicU64;
+use prometheus::core::{AtomicI64, AtomicU64};
+use prost::Message;
 use semver::Version;
+use sha2::Sha256;
 use std::error::Error;
 use std::fmt::{self, Debug};
 use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
+use std::sync::atomic::{self, AtomicU64 as AtomicU64Std};
 use std::sync::Arc;
-use std::time::UNIX_EPOCH;
+use std::time::{Duration, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::UnixStream;
 use tokio::select;
 use tokio::sync::mpsc::{self, UnboundedSender};
@@ -51,7 +60,37 @@ include!(concat!(env!("OUT_DIR"), "/mz_service.params.rs"));
 // Use with generated servers Server::new(Svc).max_decoding_message_size
 pub const MAX_GRPC_MESSAGE_SIZE: usize = usize::MAX;
 
-pub type ClientTransport = InterceptedService<Channel, VersionAttachInterceptor>;
+/// The wire compression negotiated for a gRPC connection.
+///
+/// [`GrpcClient::connect_with_deadline`] takes this as an explicit argument
+/// rather than reading it off [`GrpcClientParameters`](crate::params::GrpcClientParameters),
+/// since that struct is generated from the `mz_service.params` proto and
+/// doesn't carry a compression field; [`GrpcClient::connect`] always
+/// negotiates [`Compression::None`], so large command/response batches (most
+/// notably bulky snapshot/diff payloads on the response stream) can opt into
+/// compression only by calling `connect_with_deadline` directly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Send and accept messages uncompressed.
+    #[default]
+    None,
+    /// Compress messages with gzip.
+    Gzip,
+    /// Compress messages with zstd.
+    Zstd,
+}
+
+impl Compression {
+    fn to_tonic(self) -> Option<tonic::codec::CompressionEncoding> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some(tonic::codec::CompressionEncoding::Gzip),
+            Compression::Zstd => Some(tonic::codec::CompressionEncoding::Zstd),
+        }
+    }
+}
+
+pub type ClientTransport = InterceptedService<Channel, InterceptorChain>;
 
 /// Types that we send and receive over a service endpoint.
 pub trait ProtoServiceTypes: Debug + Clone + Send {
@@ -97,6 +136,48 @@ where
         version: Version,
         metrics: G::STATS,
         params: &GrpcClientParameters,
+    ) -> Result<Self, anyhow::Error> {
+        Self::connect_with_deadline(
+            addr,
+            version,
+            metrics,
+            params,
+            None,
+            Compression::None,
+            vec![],
+            None,
+        )
+        .await
+    }
+
+    /// Like [`GrpcClient::connect`], but additionally bounds how long the
+    /// server is allowed to spend processing each command on this stream, by
+    /// attaching a `grpc-timeout` header (see [`encode_grpc_timeout`]) to the
+    /// bidi stream's initial request, and lets the caller opt into wire
+    /// compression, extra interceptor headers, and handshake signing.
+    /// `GrpcClientParameters` is generated from the `mz_service.params` proto
+    /// and doesn't carry fields for any of these, so (unlike `connect_timeout`
+    /// and the HTTP/2 keep-alive settings below) they're taken as explicit
+    /// arguments rather than read off `params`. The server picks the shorter
+    /// of the deadline and any server-configured maximum.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn connect_with_deadline(
+        addr: String,
+        version: Version,
+        metrics: G::STATS,
+        params: &GrpcClientParameters,
+        // `GrpcClientParameters` has no `rpc_timeout` field (it's generated
+        // from the `mz_service.params` proto, which doesn't define one), so
+        // the deadline is plumbed in here instead of read off `params`.
+        deadline: Option<Duration>,
+        compression: Compression,
+        // Likewise there's no `interceptors` field on `GrpcClientParameters`;
+        // extra headers to attach via the `InterceptorChain` are passed in
+        // directly instead.
+        interceptors: Vec<(http::HeaderName, http::HeaderValue)>,
+        // And no `handshake` field either; the signing key is passed in
+        // directly by callers that want the handshake challenge attached.
+        handshake: Option<HandshakeKey>,
     ) -> Result<Self, anyhow::Error> {
         debug!("GrpcClient {}: Attempt to connect", addr);
 
@@ -124,11 +205,12 @@ where
                     .await?
             }
         };
-        let service = InterceptedService::new(channel, VersionAttachInterceptor::new(version));
-        let mut client = BidiProtoClient::new(service, G::URL, metrics);
+        let interceptors = InterceptorChain::new(version, interceptors, handshake);
+        let service = InterceptedService::new(channel, interceptors);
+        let mut client = BidiProtoClient::new(service, G::URL, metrics, compression);
         let (tx, rx) = mpsc::unbounded_channel();
         let rx = client
-            .establish_bidi_stream(UnboundedReceiverStream::new(rx))
+            .establish_bidi_stream(UnboundedReceiverStream::new(rx), deadline)
             .await?
             .into_inner();
         info!("GrpcClient {}: connected", &addr);
@@ -205,13 +287,21 @@ where
     PR: Clone + Default + prost::Message + 'static,
     S: StatsCollector<PC, PR> + 'static,
 {
-    fn new(inner: ClientTransport, path: &'static str, stats_collector: S) -> Self
+    fn new(
+        inner: ClientTransport,
+        path: &'static str,
+        stats_collector: S,
+        compression: Compression,
+    ) -> Self
     where
         Self: Sized,
     {
-        let inner = tonic::client::Grpc::new(inner)
+        let mut inner = tonic::client::Grpc::new(inner)
             .max_decoding_message_size(MAX_GRPC_MESSAGE_SIZE)
             .max_encoding_message_size(MAX_GRPC_MESSAGE_SIZE);
+        if let Some(encoding) = compression.to_tonic() {
+            inner = inner.send_compressed(encoding).accept_compressed(encoding);
+        }
         let codec = StatCodec::new(stats_collector);
         BidiProtoClient { inner, path, codec }
     }
@@ -219,6 +309,7 @@ where
     async fn establish_bidi_stream(
         &mut self,
         rx: UnboundedReceiverStream<PC>,
+        deadline: Option<Duration>,
     ) -> Result<Response<Streaming<PR>>, Status> {
         self.inner.ready().await.map_err(|e| {
             tonic::Status::new(
@@ -227,9 +318,13 @@ where
             )
         })?;
         let path = PathAndQuery::from_static(self.path);
-        self.inner
-            .streaming(rx.into_streaming_request(), path, self.codec.clone())
-            .await
+        let mut request = rx.into_streaming_request();
+        if let Some(deadline) = deadline {
+            request
+                .metadata_mut()
+                .insert(GRPC_TIMEOUT_HEADER_KEY, encode_grpc_timeout(deadline));
+        }
+        self.inner.streaming(request, path, self.codec.clone()).await
     }
 }
 
@@ -250,6 +345,10 @@ struct GrpcServerState<F> {
     cancel_tx: Mutex<oneshot::Sender<()>>,
     client_builder: F,
     metrics: PerGrpcServerMetrics,
+    /// An upper bound on the deadline a client may request via the
+    /// `grpc-timeout` header. `None` means clients may request any deadline
+    /// (or none at all).
+    max_command_timeout: Option<Duration>,
 }
 
 impl<F, G> GrpcServer<F>
@@ -263,12 +362,18 @@ where
     /// turns a `GrpcServer<ProtoCommandType, ProtoResponseType>` into a
     /// [`Service`] that represents a gRPC server. This is always encapsulated
     /// by the tonic-generated `ProtoServer::new` method for a specific Protobuf
-    /// service.
+    /// service. `service_builder` is additionally handed the negotiated
+    /// `compression`, so that it can call the generated service's
+    /// `accept_compressed`/`send_compressed` methods.
     pub fn serve<S, Fs>(
         metrics: &GrpcServerMetrics,
         listen_addr: SocketAddr,
         version: Version,
         host: Option<String>,
+        compression: Compression,
+        max_command_timeout: Option<Duration>,
+        auth_token: Option<String>,
+        handshake: Option<HandshakeKey>,
         client_builder: F,
         service_builder: Fs,
     ) -> impl Future<Output = Result<(), anyhow::Error>>
@@ -282,23 +387,32 @@ where
             + Send
             + 'static,
         S::Future: Send + 'static,
-        Fs: FnOnce(Self) -> S + Send + 'static,
+        Fs: FnOnce(Self, Compression) -> S + Send + 'static,
     {
         let (cancel_tx, _cancel_rx) = oneshot::channel();
         let state = GrpcServerState {
             cancel_tx: Mutex::new(cancel_tx),
             client_builder,
             metrics: metrics.for_server(S::NAME),
+            max_command_timeout,
         };
         let server = Self {
             state: Arc::new(state),
         };
-        let service = service_builder(server);
+        let service = service_builder(server, compression);
 
         if host.is_none() {
             warn!("no host provided; request destination host checking is disabled");
         }
-        let validation = RequestValidationLayer { version, host };
+        if handshake.is_none() {
+            warn!("no handshake key configured; clients are trusted to self-report their version");
+        }
+        let validation = RequestValidationLayer {
+            version,
+            host,
+            auth_token,
+            handshake,
+        };
 
         info!("Starting to listen on {}", listen_addr);
 
@@ -330,7 +444,8 @@ where
         PC: fmt::Debug + Send + Sync + 'static,
         PR: fmt::Debug + Send + Sync + 'static,
     {
-        info!("GrpcServer: remote client connected");
+        let session_id = self.state.metrics.next_session_id();
+        info!(session_id, "GrpcServer: remote client connected");
 
         // Install our cancellation token. This may drop an existing
         // cancellation token. We're allowed to run until someone else drops our
@@ -343,6 +458,33 @@ where
         let (cancel_tx, mut cancel_rx) = oneshot::channel();
         *self.state.cancel_tx.lock().await = cancel_tx;
 
+        let server_name = self.state.metrics.server_name;
+        self.state.metrics.active_sessions.inc();
+        let session_id_label = session_id.to_string();
+        let commands_received: DeleteOnDropCounter<'static, AtomicI64, Vec<String>> = self
+            .state
+            .metrics
+            .session_commands_received
+            .get_delete_on_drop_metric(vec![server_name.to_string(), session_id_label.clone()]);
+        let responses_sent: DeleteOnDropCounter<'static, AtomicI64, Vec<String>> = self
+            .state
+            .metrics
+            .session_responses_sent
+            .get_delete_on_drop_metric(vec![server_name.to_string(), session_id_label]);
+
+        // A client may request a deadline for how long we spend per command
+        // via the standard `grpc-timeout` header; the effective timeout is
+        // the shorter of that and any server-configured maximum.
+        let client_timeout = request
+            .metadata()
+            .get(GRPC_TIMEOUT_HEADER_KEY)
+            .and_then(|v| v.to_str().ok())
+            .and_then(decode_grpc_timeout);
+        let command_timeout = match (client_timeout, self.state.max_command_timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
         // Construct a new client and forward commands and responses until
         // canceled.
         let mut request = request.into_inner();
@@ -356,40 +498,65 @@ where
                             None => break,
                             Some(Ok(command)) => command,
                             Some(Err(e)) => {
-                                error!("error handling client: {e}");
+                                error!(session_id, "error handling client: {e}");
                                 break;
                             }
                         };
 
                         match UNIX_EPOCH.elapsed() {
                             Ok(ts) => state.metrics.last_command_received.set(ts.as_secs()),
-                            Err(e) => error!("failed to get system time: {e}"),
+                            Err(e) => error!(session_id, "failed to get system time: {e}"),
                         }
 
                         let command = match command.into_rust() {
                             Ok(command) => command,
                             Err(e) => {
-                                error!("error converting command from protobuf: {}", e);
+                                error!(session_id, "error converting command from protobuf: {}", e);
                                 break;
                             }
                         };
 
-                        if let Err(e) = client.send(command).await {
+                        commands_received.inc();
+                        let send = client.send(command);
+                        let result = match command_timeout {
+                            Some(timeout) => match tokio::time::timeout(timeout, send).await {
+                                Ok(result) => result,
+                                Err(_) => {
+                                    yield Err(Status::cancelled("Timeout expired"));
+                                    continue;
+                                }
+                            },
+                            None => send.await,
+                        };
+                        if let Err(e) = result {
                             yield Err(Status::unknown(e.to_string()));
                         }
                     }
-                    response = client.recv() => {
+                    response = async {
+                        match command_timeout {
+                            Some(timeout) => tokio::time::timeout(timeout, client.recv()).await,
+                            None => Ok(client.recv().await),
+                        }
+                    } => {
                         match response {
-                            Ok(Some(response)) => yield Ok(response.into_proto()),
-                            Ok(None) => break,
-                            Err(e) => yield Err(Status::unknown(e.to_string())),
+                            Ok(Ok(Some(response))) => {
+                                responses_sent.inc();
+                                yield Ok(response.into_proto())
+                            }
+                            Ok(Ok(None)) => break,
+                            Ok(Err(e)) => yield Err(Status::unknown(e.to_string())),
+                            Err(_) => yield Err(Status::cancelled("Timeout expired")),
                         }
                     }
                     _ = &mut cancel_rx => break,
                 }
             }
         };
-        Ok(Response::new(ResponseStream::new(stream)))
+        Ok(Response::new(ResponseStream::new(
+            stream,
+            session_id,
+            self.state.metrics.active_sessions.clone(),
+        )))
     }
 }
 
@@ -397,14 +564,22 @@ where
 ///
 /// This is defined as a struct, rather than a type alias, so that we can define a `Drop` impl that
 /// logs stream termination.
-pub struct ResponseStream<PR>(Pin<Box<dyn Stream<Item = Result<PR, Status>> + Send>>);
+pub struct ResponseStream<PR> {
+    inner: Pin<Box<dyn Stream<Item = Result<PR, Status>> + Send>>,
+    session_id: u64,
+    active_sessions: UIntGauge,
+}
 
 impl<PR> ResponseStream<PR> {
-    fn new<S>(stream: S) -> Self
+    fn new<S>(stream: S, session_id: u64, active_sessions: UIntGauge) -> Self
     where
         S: Stream<Item = Result<PR, Status>> + Send + 'static,
     {
-        Self(Box::pin(stream))
+        Self {
+            inner: Box::pin(stream),
+            session_id,
+            active_sessions,
+        }
     }
 }
 
@@ -415,20 +590,277 @@ impl<PR> Stream for ResponseStream<PR> {
         mut self: Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        self.0.poll_next_unpin(cx)
+        self.inner.poll_next_unpin(cx)
     }
 }
 
 impl<PR> Drop for ResponseStream<PR> {
     fn drop(&mut self) {
-        info!("GrpcServer: response stream disconnected");
+        self.active_sessions.dec();
+        info!(session_id = self.session_id, "GrpcServer: response stream disconnected");
+    }
+}
+
+/// A client to a remote dataflow server using QUIC and protobuf based
+/// communication.
+///
+/// This is an alternative to [`GrpcClient`] that opens a single QUIC
+/// bidirectional stream instead of an HTTP/2 stream. Because QUIC multiplexes
+/// independent streams over a single connection without head-of-line
+/// blocking, this transport is preferable for high-volume dataflow traffic
+/// sharing a connection with other streams. The wire format is a sequence of
+/// frames, each a 4-byte big-endian length prefix followed by that many bytes
+/// of `prost`-encoded message, identical in spirit to the framing gRPC itself
+/// uses for messages within an HTTP/2 stream.
+#[derive(Debug)]
+pub struct QuicClient<G>
+where
+    G: ProtoServiceTypes,
+{
+    send: quinn::SendStream,
+    recv: quinn::RecvStream,
+    stats: G::STATS,
+    _marker: PhantomData<G>,
+}
+
+impl<G> QuicClient<G>
+where
+    G: ProtoServiceTypes,
+{
+    /// Connects to the server at the given address, announcing the specified
+    /// client version.
+    ///
+    /// On success, a single bidirectional QUIC stream has been opened and the
+    /// version/host handshake frame has been written to it. This does not wait
+    /// for the server to accept the handshake.
+    pub async fn connect(
+        addr: String,
+        version: Version,
+        host: Option<String>,
+        stats: G::STATS,
+        endpoint: &quinn::Endpoint,
+    ) -> Result<Self, anyhow::Error> {
+        debug!("QuicClient {}: attempt to connect", addr);
+
+        let socket_addr: std::net::SocketAddr = addr.parse()?;
+        let connection = endpoint
+            .connect(socket_addr, &addr)?
+            .await
+            .map_err(|e| anyhow::anyhow!("QUIC connection to {addr} failed: {e}"))?;
+        let (mut send, mut recv) = connection.open_bi().await?;
+
+        let handshake = QuicHandshake {
+            version: version.to_string(),
+            host,
+        };
+        write_frame(&mut send, &handshake.encode()).await?;
+
+        info!("QuicClient {}: connected", &addr);
+        Ok(QuicClient {
+            send,
+            recv,
+            stats,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[async_trait]
+impl<G, C, R> GenericClient<C, R> for QuicClient<G>
+where
+    C: RustType<G::PC> + Send + Sync + 'static,
+    R: RustType<G::PR> + Send + Sync + 'static,
+    G: ProtoServiceTypes,
+{
+    async fn send(&mut self, cmd: C) -> Result<(), anyhow::Error> {
+        let proto = cmd.into_proto();
+        let bytes = proto.encode_to_vec();
+        self.stats.send_event(&proto, bytes.len());
+        write_frame(&mut self.send, &bytes).await?;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<Option<R>, anyhow::Error> {
+        let Some(bytes) = read_frame(&mut self.recv).await? else {
+            return Ok(None);
+        };
+        let proto = G::PR::decode(bytes.as_slice())?;
+        self.stats.recv_event(&proto, bytes.len());
+        Ok(Some(proto.into_rust()?))
+    }
+}
+
+/// A QUIC server that stitches a single bidirectional stream to a
+/// [`GenericClient`].
+///
+/// It is the QUIC counterpart of [`GrpcServer`]. It does not accept
+/// connections itself: the caller accepts them (for example from a
+/// `quinn::Endpoint`) in a loop and hands each one off to
+/// [`QuicServer::forward_bidi_stream`].
+pub struct QuicServer<F> {
+    client_builder: Arc<F>,
+    version: Version,
+    host: Option<String>,
+}
+
+impl<F, G> QuicServer<F>
+where
+    F: Fn() -> G + Send + Sync + 'static,
+{
+    /// Creates a new server that validates incoming connections against
+    /// `version`/`host`, matching [`RequestValidation`]'s semantics.
+    pub fn new(client_builder: F, version: Version, host: Option<String>) -> Self {
+        QuicServer {
+            client_builder: Arc::new(client_builder),
+            version,
+            host,
+        }
+    }
+
+    /// Accepts a single bidi stream on an established QUIC `connection`,
+    /// validates its handshake frame, and forwards commands/responses between
+    /// that stream and a freshly built client until either side ends.
+    ///
+    /// Unlike [`GrpcServer::forward_bidi_stream`], forwarding is in lockstep:
+    /// after each command the server waits for exactly one response from the
+    /// client and writes it before reading the next command. Responses are
+    /// not forwarded while the server waits for a command.
+    pub async fn forward_bidi_stream<C, R, PC, PR>(
+        &self,
+        connection: quinn::Connection,
+    ) -> Result<(), anyhow::Error>
+    where
+        G: GenericClient<C, R> + 'static,
+        C: RustType<PC> + Send + Sync + 'static + fmt::Debug,
+        R: RustType<PR> + Send + Sync + 'static + fmt::Debug,
+        PC: prost::Message + Default + Send + Sync + 'static + fmt::Debug,
+        PR: prost::Message + Send + Sync + 'static + fmt::Debug,
+    {
+        info!("QuicServer: remote client connected");
+        let (mut send, mut recv) = connection.accept_bi().await?;
+
+        let Some(handshake_bytes) = read_frame(&mut recv).await? else {
+            anyhow::bail!("QUIC connection closed before handshake");
+        };
+        let handshake = QuicHandshake::decode(&handshake_bytes)?;
+        if handshake.version != self.version.to_string() {
+            anyhow::bail!(
+                "client presented version {:?} but {:?} required",
+                handshake.version,
+                self.version.to_string()
+            );
+        }
+        if let (Some(host), Some(required)) = (&handshake.host, &self.host) {
+            if host != required {
+                anyhow::bail!("client presented host {host:?} but {required:?} required");
+            }
+        }
+
+        let mut client = (self.client_builder)();
+        loop {
+            let Some(bytes) = read_frame(&mut recv).await? else {
+                break;
+            };
+            let command = PC::decode(bytes.as_slice())?;
+            let command = command.into_rust()?;
+            client.send(command).await?;
+
+            match client.recv().await? {
+                Some(response) => {
+                    let proto = response.into_proto();
+                    write_frame(&mut send, &proto.encode_to_vec()).await?;
+                }
+                None => break,
+            }
+        }
+        info!("QuicServer: response stream disconnected");
+        Ok(())
+    }
+}
+
+/// The first frame sent on a [`QuicClient`]/[`QuicServer`] stream, playing
+/// the same role that [`VersionAttachInterceptor`]/[`RequestValidation`] play
+/// for the gRPC transport.
+struct QuicHandshake {
+    version: String,
+    host: Option<String>,
+}
+
+impl QuicHandshake {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(self.version.len() as u32).to_be_bytes());
+        buf.extend_from_slice(self.version.as_bytes());
+        match &self.host {
+            Some(host) => {
+                buf.push(1);
+                buf.extend_from_slice(&(host.len() as u32).to_be_bytes());
+                buf.extend_from_slice(host.as_bytes());
+            }
+            None => buf.push(0),
+        }
+        buf
+    }
+
+    fn decode(mut buf: &[u8]) -> Result<Self, anyhow::Error> {
+        let version_len = read_u32(&mut buf)? as usize;
+        anyhow::ensure!(buf.len() >= version_len, "truncated handshake frame");
+        let version = String::from_utf8(buf[..version_len].to_vec())?;
+        buf = &buf[version_len..];
+
+        anyhow::ensure!(!buf.is_empty(), "truncated handshake frame");
+        let has_host = buf[0] == 1;
+        buf = &buf[1..];
+        let host = if has_host {
+            let host_len = read_u32(&mut buf)? as usize;
+            anyhow::ensure!(buf.len() >= host_len, "truncated handshake frame");
+            Some(String::from_utf8(buf[..host_len].to_vec())?)
+        } else {
+            None
+        };
+
+        Ok(QuicHandshake { version, host })
     }
 }
 
+fn read_u32(buf: &mut &[u8]) -> Result<u32, anyhow::Error> {
+    anyhow::ensure!(buf.len() >= 4, "truncated frame length");
+    let (len_bytes, rest) = buf.split_at(4);
+    *buf = rest;
+    Ok(u32::from_be_bytes(len_bytes.try_into().unwrap()))
+}
+
+/// Writes `payload` to `stream` as a single length-prefixed frame.
+async fn write_frame(stream: &mut quinn::SendStream, payload: &[u8]) -> Result<(), anyhow::Error> {
+    stream
+        .write_all(&(payload.len() as u32).to_be_bytes())
+        .await?;
+    stream.write_all(payload).await?;
+    Ok(())
+}
+
+/// Reads a single length-prefixed frame from `stream`, returning `None` on a
+/// clean end-of-stream before any bytes of the next frame have arrived.
+async fn read_frame(stream: &mut quinn::RecvStream) -> Result<Option<Vec<u8>>, anyhow::Error> {
+    let mut len_buf = [0u8; 4];
+    match stream.read_exact(&mut len_buf).await {
+        Ok(()) => {}
+        Err(quinn::ReadExactError::FinishedEarly(0)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_be_bytes(len_buf) as usize;
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).await?;
+    Ok(Some(payload))
+}
+
 /// Metrics for a [`GrpcServer`].
 #[derive(Debug)]
 pub struct GrpcServerMetrics {
     last_command_received: UIntGaugeVec,
+    active_sessions: UIntGaugeVec,
+    session_commands_received: IntCounterVec,
+    session_responses_sent: IntCounterVec,
 }
 
 impl GrpcServerMetrics {
@@ -440,25 +872,100 @@ impl GrpcServerMetrics {
                 help: "The time at which the server received its last command.",
                 var_labels: ["server_name"],
             )),
+            active_sessions: registry.register(metric!(
+                name: "mz_grpc_server_active_sessions",
+                help: "The number of bidi streams currently connected to the server.",
+                var_labels: ["server_name"],
+            )),
+            session_commands_received: registry.register(metric!(
+                name: "mz_grpc_server_session_commands_received",
+                help: "The number of commands received on a session's bidi stream.",
+                var_labels: ["server_name", "session_id"],
+            )),
+            session_responses_sent: registry.register(metric!(
+                name: "mz_grpc_server_session_responses_sent",
+                help: "The number of responses sent on a session's bidi stream.",
+                var_labels: ["server_name", "session_id"],
+            )),
         }
     }
 
     fn for_server(&self, name: &'static str) -> PerGrpcServerMetrics {
         PerGrpcServerMetrics {
+            server_name: name,
             last_command_received: self
                 .last_command_received
                 .get_delete_on_drop_metric(vec![name]),
+            active_sessions: self.active_sessions.with_label_values(&[name]),
+            session_commands_received: self.session_commands_received.clone(),
+            session_responses_sent: self.session_responses_sent.clone(),
+            next_session_id: AtomicU64Std::new(0),
         }
     }
 }
 
 #[derive(Debug)]
 struct PerGrpcServerMetrics {
+    server_name: &'static str,
     last_command_received: DeleteOnDropGauge<'static, AtomicU64, Vec<&'static str>>,
+    /// The number of sessions (bidi streams) currently connected. Unlike
+    /// `last_command_received`, this is a plain (non-delete-on-drop) gauge:
+    /// it's incremented and decremented many times over the server's
+    /// lifetime, once per session, rather than set once per server.
+    active_sessions: UIntGauge,
+    /// Per-session counters, keyed by `(server_name, session_id)`. Each
+    /// session registers its own handle from this vec for the duration of
+    /// its stream.
+    session_commands_received: IntCounterVec,
+    session_responses_sent: IntCounterVec,
+    /// A monotonically increasing counter used to assign each accepted bidi
+    /// stream a unique session id, for correlating logs and metrics across a
+    /// connection's lifetime.
+    next_session_id: AtomicU64Std,
+}
+
+impl PerGrpcServerMetrics {
+    /// Allocates a new, never-before-used session id.
+    fn next_session_id(&self) -> u64 {
+        self.next_session_id.fetch_add(1, atomic::Ordering::Relaxed)
+    }
 }
 
 const VERSION_HEADER_KEY: &str = "x-mz-version";
 
+/// The standard gRPC metadata key used to propagate a per-call deadline.
+const GRPC_TIMEOUT_HEADER_KEY: &str = "grpc-timeout";
+
+/// Encodes `duration` using the standard `grpc-timeout` encoding: an integer
+/// followed by a unit suffix (`H`ours, `M`inutes, `S`econds, `m`illiseconds,
+/// `u`econds, `n`anoseconds), e.g. `"500m"` for 500 milliseconds.
+///
+/// We always encode in milliseconds, which is granular enough for our
+/// purposes and keeps the encoding simple.
+fn encode_grpc_timeout(duration: Duration) -> AsciiMetadataValue {
+    let millis = duration.as_millis();
+    format!("{millis}m")
+        .try_into()
+        .expect("formatted integer plus unit suffix is a valid metadata value")
+}
+
+/// Decodes a `grpc-timeout` header value into a [`Duration`], per the
+/// encoding documented on [`encode_grpc_timeout`].
+fn decode_grpc_timeout(value: &str) -> Option<Duration> {
+    let (digits, unit) = value.split_at(value.len().checked_sub(1)?);
+    let amount: u64 = digits.parse().ok()?;
+    let duration = match unit {
+        "H" => Duration::from_secs(amount.checked_mul(3600)?),
+        "M" => Duration::from_secs(amount.checked_mul(60)?),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+    Some(duration)
+}
+
 /// A gRPC interceptor that attaches a version as metadata to each request.
 #[derive(Debug, Clone)]
 pub struct VersionAttachInterceptor {
@@ -485,11 +992,188 @@ impl Interceptor for VersionAttachInterceptor {
     }
 }
 
+/// The metadata key carrying a bearer/auth token, checked by
+/// [`RequestValidation`] when the server is configured with a shared secret.
+const AUTH_TOKEN_HEADER_KEY: &str = "x-mz-auth-token";
+
+/// The metadata key carrying the timestamp (seconds since the Unix epoch,
+/// as decimal ASCII) that [`HandshakeKey::sign`] signed over.
+const HANDSHAKE_TIMESTAMP_HEADER_KEY: &str = "x-mz-handshake-timestamp";
+
+/// The metadata key carrying the hex-encoded HMAC-SHA256 signature produced
+/// by [`HandshakeKey::sign`].
+const HANDSHAKE_SIGNATURE_HEADER_KEY: &str = "x-mz-handshake-signature";
+
+/// Signatures older than this are rejected, bounding how long a captured
+/// handshake token remains replayable.
+const HANDSHAKE_TOKEN_TTL: Duration = Duration::from_secs(60);
+
+/// A shared secret used to sign and verify the control-connection handshake
+/// exchanged between [`GrpcClient`] and [`RequestValidation`].
+///
+/// The signature binds the client's declared version to a timestamp, so a
+/// captured token can't be replayed past [`HANDSHAKE_TOKEN_TTL`]. This is
+/// meant to harden the control connection against spoofed clients in
+/// multi-tenant environments where the transport itself isn't
+/// mTLS-protected; it is not a substitute for transport security where that
+/// is available.
+#[derive(Clone)]
+pub struct HandshakeKey(Arc<[u8]>);
+
+impl HandshakeKey {
+    /// Creates a new handshake key from raw shared-secret bytes.
+    pub fn new(key: impl Into<Vec<u8>>) -> HandshakeKey {
+        HandshakeKey(key.into().into())
+    }
+
+    fn sign(&self, version: &Version, timestamp: u64) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0)
+            .expect("HMAC accepts a key of any length");
+        mac.update(version.to_string().as_bytes());
+        mac.update(&timestamp.to_be_bytes());
+        encode_hex(&mac.finalize().into_bytes())
+    }
+
+    fn verify(&self, version: &Version, timestamp: u64, signature: &str) -> bool {
+        let Some(signature) = decode_hex(signature) else {
+            return false;
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0)
+            .expect("HMAC accepts a key of any length");
+        mac.update(version.to_string().as_bytes());
+        mac.update(&timestamp.to_be_bytes());
+        // `Mac::verify_slice` compares the computed and presented tags in
+        // constant time, unlike comparing their hex encodings with `==`,
+        // which would leak timing information about a secret we're
+        // specifically trying to protect from spoofed clients.
+        mac.verify_slice(&signature).is_ok()
+    }
+}
+
+impl Debug for HandshakeKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HandshakeKey").field(&"<redacted>").finish()
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(out, "{byte:02x}").expect("writing to a String never fails");
+    }
+    out
+}
+
+/// The inverse of [`encode_hex`]. Returns `None` if `hex` has odd length or
+/// contains a pair of characters that is not a base-16 number. Uppercase
+/// digits are accepted as well as lowercase ones.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Compares two byte strings for equality so that, for inputs of equal
+/// length, the time taken does not depend on the number of matching leading
+/// bytes of the secret being compared against (here, the request's auth
+/// token). Inputs of different lengths return `false` early, so the length
+/// of the secret is not hidden.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// A composable chain of client interceptors.
+///
+/// [`VersionAttachInterceptor`] is always applied first, followed by any
+/// additional metadata the caller passed as `interceptors` to
+/// [`GrpcClient::connect_with_deadline`] — most importantly a bearer/auth
+/// token or trace-context propagation headers, for deployments that front
+/// the dataflow servers with an authenticating proxy.
+#[derive(Clone)]
+pub struct InterceptorChain {
+    version: VersionAttachInterceptor,
+    version_semver: Version,
+    headers: Vec<(tonic::metadata::AsciiMetadataKey, AsciiMetadataValue)>,
+    handshake: Option<HandshakeKey>,
+}
+
+impl InterceptorChain {
+    fn new(
+        version: Version,
+        headers: Vec<(http::HeaderName, http::HeaderValue)>,
+        handshake: Option<HandshakeKey>,
+    ) -> Self {
+        let headers = headers
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let key = tonic::metadata::AsciiMetadataKey::from_bytes(name.as_ref()).ok()?;
+                let value = AsciiMetadataValue::from_bytes(value.as_bytes()).ok()?;
+                Some((key, value))
+            })
+            .collect();
+        InterceptorChain {
+            version: VersionAttachInterceptor::new(version.clone()),
+            version_semver: version,
+            headers,
+            handshake,
+        }
+    }
+}
+
+impl Interceptor for InterceptorChain {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let mut request = self.version.call(request)?;
+        for (key, value) in &self.headers {
+            request.metadata_mut().insert(key.clone(), value.clone());
+        }
+        if let Some(handshake) = &self.handshake {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|_| Status::internal("client clock is before the Unix epoch"))?
+                .as_secs();
+            let signature = handshake.sign(&self.version_semver, timestamp);
+            request.metadata_mut().insert(
+                HANDSHAKE_TIMESTAMP_HEADER_KEY,
+                timestamp
+                    .to_string()
+                    .try_into()
+                    .expect("decimal integer is a valid metadata value"),
+            );
+            request.metadata_mut().insert(
+                HANDSHAKE_SIGNATURE_HEADER_KEY,
+                signature
+                    .try_into()
+                    .expect("hex string is a valid metadata value"),
+            );
+        }
+        Ok(request)
+    }
+}
+
 /// A `tower` layer that validates requests for compatibility with the server.
 #[derive(Clone)]
 struct RequestValidationLayer {
     version: Version,
     host: Option<String>,
+    /// When set, requests must present this value in the
+    /// [`AUTH_TOKEN_HEADER_KEY`] header.
+    auth_token: Option<String>,
+    /// When set, requests must present a fresh, validly-signed handshake
+    /// token in addition to the version header, which is always required. See
+    /// [`HandshakeKey`].
+    handshake: Option<HandshakeKey>,
 }
 
 impl<S> tower::Layer<S> for RequestValidationLayer {
@@ -503,8 +1187,11 @@ impl<S> tower::Layer<S> for RequestValidationLayer {
             .expect("version is a valid header value");
         RequestValidation {
             inner,
+            expected_version: self.version.clone(),
             version,
             host: self.host.clone(),
+            auth_token: self.auth_token.clone(),
+            handshake: self.handshake.clone(),
         }
     }
 }
@@ -513,8 +1200,11 @@ impl<S> tower::Layer<S> for RequestValidationLayer {
 #[derive(Clone)]
 struct RequestValidation<S> {
     inner: S,
+    expected_version: Version,
     version: http::HeaderValue,
     host: Option<String>,
+    auth_token: Option<String>,
+    handshake: Option<HandshakeKey>,
 }
 
 impl<S, B> Service<http::Request<B>> for RequestValidation<S>
@@ -559,6 +1249,114 @@ where
             }
         }
 
+        if let Some(expected_token) = &self.auth_token {
+            match req.headers().get(AUTH_TOKEN_HEADER_KEY) {
+                Some(token) if constant_time_eq(token.as_bytes(), expected_token.as_bytes()) => {}
+                Some(_) => return error("request presented an invalid auth token".into()),
+                None => return error("request missing auth token header".into()),
+            }
+        }
+
+        if let Some(handshake) = &self.handshake {
+            let Some(timestamp) = req.headers().get(HANDSHAKE_TIMESTAMP_HEADER_KEY) else {
+                return error("request missing handshake timestamp header".into());
+            };
+            let Some(signature) = req.headers().get(HANDSHAKE_SIGNATURE_HEADER_KEY) else {
+                return error("request missing handshake signature header".into());
+            };
+            let (Ok(timestamp), Ok(signature)) = (timestamp.to_str(), signature.to_str()) else {
+                return error("handshake headers are not valid ASCII".into());
+            };
+            let Ok(timestamp) = timestamp.parse::<u64>() else {
+                return error("handshake timestamp is not a valid integer".into());
+            };
+            let now = match std::time::SystemTime::now().duration_since(UNIX_EPOCH) {
+                Ok(now) => now,
+                Err(_) => return error("server clock is before the Unix epoch".into()),
+            };
+            if now.as_secs().abs_diff(timestamp) > HANDSHAKE_TOKEN_TTL.as_secs() {
+                return error("handshake token has expired".into());
+            }
+            if !handshake.verify(&self.expected_version, timestamp, signature) {
+                return error("handshake signature is invalid".into());
+            }
+        }
+
         Box::pin(self.inner.call(req))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_quic_handshake_round_trip() {
+        for handshake in [
+            QuicHandshake {
+                version: "1.2.3".into(),
+                host: Some("materialized-0".into()),
+            },
+            QuicHandshake {
+                version: "1.2.3".into(),
+                host: None,
+            },
+        ] {
+            let decoded = QuicHandshake::decode(&handshake.encode()).expect("valid frame");
+            assert_eq!(decoded.version, handshake.version);
+            assert_eq!(decoded.host, handshake.host);
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_grpc_timeout_round_trip() {
+        for duration in [
+            Duration::from_millis(0),
+            Duration::from_millis(1),
+            Duration::from_millis(500),
+            Duration::from_secs(30),
+            Duration::from_secs(3_600),
+        ] {
+            let encoded = encode_grpc_timeout(duration);
+            let decoded =
+                decode_grpc_timeout(encoded.to_str().expect("valid ASCII")).expect("valid value");
+            // We always encode in milliseconds (see encode_grpc_timeout's doc
+            // comment), so the round trip is exact even though decode_grpc_timeout
+            // also accepts coarser units.
+            assert_eq!(decoded, duration);
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_decode_grpc_timeout_rejects_garbage() {
+        assert_eq!(decode_grpc_timeout(""), None);
+        assert_eq!(decode_grpc_timeout("m"), None);
+        assert_eq!(decode_grpc_timeout("500x"), None);
+        assert_eq!(decode_grpc_timeout("abcm"), None);
+    }
+
+    #[mz_ore::test]
+    fn test_handshake_key_sign_verify_round_trip() {
+        let key = HandshakeKey::new(b"shared-secret".to_vec());
+        let version = Version::new(1, 2, 3);
+        let signature = key.sign(&version, 1_000);
+        assert!(key.verify(&version, 1_000, &signature));
+    }
+
+    #[mz_ore::test]
+    fn test_handshake_key_verify_rejects_tampering() {
+        let key = HandshakeKey::new(b"shared-secret".to_vec());
+        let other_key = HandshakeKey::new(b"different-secret".to_vec());
+        let version = Version::new(1, 2, 3);
+        let signature = key.sign(&version, 1_000);
+
+        // Wrong key.
+        assert!(!other_key.verify(&version, 1_000, &signature));
+        // Wrong version.
+        assert!(!key.verify(&Version::new(1, 2, 4), 1_000, &signature));
+        // Wrong timestamp.
+        assert!(!key.verify(&version, 1_001, &signature));
+        // Not even valid hex.
+        assert!(!key.verify(&version, 1_000, "not-hex"));
+    }
+}